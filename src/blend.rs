@@ -0,0 +1,36 @@
+use gl33::*;
+
+use crate::gl::Gl;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlendMode {
+    None,
+    Alpha,
+    Additive,
+    Multiply,
+}
+
+impl BlendMode {
+    pub(crate) fn apply(self, gl: &Gl) {
+        unsafe {
+            match self {
+                BlendMode::None => gl.Disable(GL_BLEND),
+                BlendMode::Alpha => {
+                    gl.Enable(GL_BLEND);
+                    gl.BlendEquation(GL_FUNC_ADD);
+                    gl.BlendFunc(GL_SRC_ALPHA, GL_ONE_MINUS_SRC_ALPHA);
+                }
+                BlendMode::Additive => {
+                    gl.Enable(GL_BLEND);
+                    gl.BlendEquation(GL_FUNC_ADD);
+                    gl.BlendFunc(GL_SRC_ALPHA, GL_ONE);
+                }
+                BlendMode::Multiply => {
+                    gl.Enable(GL_BLEND);
+                    gl.BlendEquation(GL_FUNC_ADD);
+                    gl.BlendFunc(GL_DST_COLOR, GL_ZERO);
+                }
+            }
+        }
+    }
+}
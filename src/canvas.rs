@@ -0,0 +1,71 @@
+use gl33::*;
+
+use crate::gl::Gl;
+use crate::graphics::RenderTarget;
+use crate::{Graphics, GraphicsScope, Texture};
+
+pub struct Canvas {
+    gl: Gl,
+    fbo: u32,
+    renderbuffer: u32,
+    texture: Texture,
+    width: u32,
+    height: u32,
+}
+
+impl Drop for Canvas {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteFramebuffers(1, &self.fbo);
+            self.gl.DeleteRenderbuffers(1, &self.renderbuffer);
+        }
+    }
+}
+
+impl Canvas {
+    pub fn new(gl: Gl, width: u32, height: u32) -> Self {
+        // Nothing in this renderer enables depth testing (`depth` on `GraphicsScope` is only a
+        // CPU-side painter's-algorithm sort key), so a canvas has no use for a depth/stencil
+        // attachment.
+        let (fbo, texture_id, renderbuffer) = unsafe {
+            gl.create_framebuffer(
+                width as _,
+                height as _,
+                GL_RGBA as _,
+                GL_RGBA,
+                GL_UNSIGNED_BYTE,
+                false,
+            )
+        };
+
+        Self {
+            texture: Texture::from_raw(gl.clone(), texture_id, width, height),
+            gl,
+            fbo,
+            renderbuffer,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The canvas' color attachment, sampleable as a regular `Texture` once drawing into it is done.
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    pub fn push<'a>(&self, graphics: &'a mut Graphics) -> GraphicsScope<'a> {
+        graphics.push_to(RenderTarget::Canvas {
+            fbo: self.fbo,
+            width: self.width,
+            height: self.height,
+        })
+    }
+}
@@ -0,0 +1,17 @@
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const WHITE: Color = Color::new(1., 1., 1., 1.);
+    pub const BLACK: Color = Color::new(0., 0., 0., 1.);
+    pub const CORNFLOWER_BLUE: Color = Color::new(0.392, 0.584, 0.929, 1.);
+}
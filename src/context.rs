@@ -0,0 +1,74 @@
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Number of frames averaged together to produce a smoothed `fps()` reading.
+const FPS_SAMPLE_COUNT: usize = 30;
+
+#[derive(Clone)]
+pub struct Context {
+    inner: Rc<Inner>,
+}
+
+struct Inner {
+    width: Cell<u32>,
+    height: Cell<u32>,
+    last_frame_time: Cell<f32>,
+    frame_times: RefCell<VecDeque<f32>>,
+}
+
+impl Context {
+    pub(crate) fn new(width: u32, height: u32) -> Self {
+        Self {
+            inner: Rc::new(Inner {
+                width: Cell::new(width),
+                height: Cell::new(height),
+                last_frame_time: Cell::new(0.),
+                frame_times: RefCell::new(VecDeque::with_capacity(FPS_SAMPLE_COUNT)),
+            }),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.inner.width.get()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.inner.height.get()
+    }
+
+    pub(crate) fn set_size(&self, width: u32, height: u32) {
+        self.inner.width.set(width);
+        self.inner.height.set(height);
+    }
+
+    /// Seconds elapsed during the previous frame.
+    pub fn last_frame_time(&self) -> f32 {
+        self.inner.last_frame_time.get()
+    }
+
+    /// Frames per second, smoothed over the last [`FPS_SAMPLE_COUNT`] frames.
+    pub fn fps(&self) -> f32 {
+        let frame_times = self.inner.frame_times.borrow();
+        if frame_times.is_empty() {
+            return 0.;
+        }
+
+        let average = frame_times.iter().sum::<f32>() / frame_times.len() as f32;
+        if average > 0. {
+            1. / average
+        } else {
+            0.
+        }
+    }
+
+    pub(crate) fn push_frame_time(&self, delta: f32) {
+        self.inner.last_frame_time.set(delta);
+
+        let mut frame_times = self.inner.frame_times.borrow_mut();
+        frame_times.push_back(delta);
+        if frame_times.len() > FPS_SAMPLE_COUNT {
+            frame_times.pop_front();
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::gl::Gl;
+use crate::Texture;
+
+#[derive(Deserialize)]
+struct AtlasJson {
+    width: f32,
+    height: f32,
+    characters: HashMap<char, GlyphJson>,
+}
+
+#[derive(Deserialize)]
+struct GlyphJson {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    #[serde(rename = "originX")]
+    origin_x: f32,
+    #[serde(rename = "originY")]
+    origin_y: f32,
+    advance: f32,
+}
+
+pub(crate) struct Glyph {
+    pub(crate) x: f32,
+    pub(crate) y: f32,
+    pub(crate) width: f32,
+    pub(crate) height: f32,
+    pub(crate) origin_x: f32,
+    pub(crate) origin_y: f32,
+    pub(crate) advance: f32,
+}
+
+pub struct Font {
+    pub(crate) texture: Texture,
+    pub(crate) atlas_width: f32,
+    pub(crate) atlas_height: f32,
+    pub(crate) glyphs: HashMap<char, Glyph>,
+    pub(crate) line_height: f32,
+    pub(crate) space_advance: f32,
+}
+
+/// Derives per-glyph data, line height, and the fallback space advance from a parsed atlas.
+fn glyphs_from_atlas(atlas: AtlasJson) -> (HashMap<char, Glyph>, f32, f32) {
+    let space_advance = atlas
+        .characters
+        .get(&' ')
+        .map(|glyph| glyph.advance)
+        .unwrap_or(atlas.width / 4.);
+    let line_height = atlas
+        .characters
+        .values()
+        .fold(0.0_f32, |max, glyph| max.max(glyph.height));
+
+    let glyphs = atlas
+        .characters
+        .into_iter()
+        .map(|(c, glyph)| {
+            (
+                c,
+                Glyph {
+                    x: glyph.x,
+                    y: glyph.y,
+                    width: glyph.width,
+                    height: glyph.height,
+                    origin_x: glyph.origin_x,
+                    origin_y: glyph.origin_y,
+                    advance: glyph.advance,
+                },
+            )
+        })
+        .collect();
+
+    (glyphs, line_height, space_advance)
+}
+
+impl Font {
+    pub fn from_file(gl: Gl, atlas_path: &str, image_path: &str) -> Result<Self, String> {
+        let atlas_json = fs::read_to_string(atlas_path).map_err(|e| e.to_string())?;
+        let atlas: AtlasJson = serde_json::from_str(&atlas_json).map_err(|e| e.to_string())?;
+        let texture = Texture::from_file(gl, image_path)?;
+
+        let atlas_width = atlas.width;
+        let atlas_height = atlas.height;
+        let (glyphs, line_height, space_advance) = glyphs_from_atlas(atlas);
+
+        Ok(Self {
+            texture,
+            atlas_width,
+            atlas_height,
+            glyphs,
+            line_height,
+            space_advance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_glyphs_and_derives_line_height() {
+        let json = r#"{
+            "width": 64.0,
+            "height": 32.0,
+            "characters": {
+                "A": {"x": 0.0, "y": 0.0, "width": 8.0, "height": 10.0, "originX": 1.0, "originY": 2.0, "advance": 9.0},
+                "B": {"x": 8.0, "y": 0.0, "width": 8.0, "height": 6.0, "originX": 0.0, "originY": 0.0, "advance": 7.0}
+            }
+        }"#;
+        let atlas: AtlasJson = serde_json::from_str(json).unwrap();
+        let (glyphs, line_height, space_advance) = glyphs_from_atlas(atlas);
+
+        let a = glyphs.get(&'A').unwrap();
+        assert_eq!((a.x, a.y, a.width, a.height), (0., 0., 8., 10.));
+        assert_eq!((a.origin_x, a.origin_y, a.advance), (1., 2., 9.));
+
+        // Tallest glyph sets the line height.
+        assert_eq!(line_height, 10.);
+        // No space glyph in the atlas, so space_advance falls back to a quarter of atlas width.
+        assert_eq!(space_advance, 16.);
+    }
+
+    #[test]
+    fn space_advance_uses_the_atlas_space_glyph_when_present() {
+        let json = r#"{
+            "width": 64.0,
+            "height": 32.0,
+            "characters": {
+                " ": {"x": 0.0, "y": 0.0, "width": 4.0, "height": 4.0, "originX": 0.0, "originY": 0.0, "advance": 5.0}
+            }
+        }"#;
+        let atlas: AtlasJson = serde_json::from_str(json).unwrap();
+        let (_, _, space_advance) = glyphs_from_atlas(atlas);
+
+        assert_eq!(space_advance, 5.);
+    }
+}
@@ -0,0 +1,216 @@
+use std::time::Instant;
+
+use gl33::GlFns;
+use glutin::dpi::LogicalSize;
+use glutin::event::{ElementState, Event as GlutinEvent, MouseButton as GlutinMouseButton, VirtualKeyCode, WindowEvent};
+use glutin::event_loop::{ControlFlow, EventLoop};
+use glutin::window::{Window, WindowBuilder};
+use glutin::{ContextBuilder, ContextWrapper, GlProfile, PossiblyCurrent};
+
+use crate::event::{Key, MouseButton};
+use crate::gl::Gl;
+use crate::{Context, Event, Graphics};
+
+pub struct GameBuilder {
+    title: String,
+    width: u32,
+    height: u32,
+}
+
+impl Default for GameBuilder {
+    fn default() -> Self {
+        Self {
+            title: "ramune".to_string(),
+            width: 800,
+            height: 600,
+        }
+    }
+}
+
+impl GameBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn build(self) -> (Game, Context) {
+        let event_loop = EventLoop::new();
+        let window_builder = WindowBuilder::new()
+            .with_title(self.title)
+            .with_inner_size(LogicalSize::new(self.width, self.height));
+
+        let windowed_context = ContextBuilder::new()
+            .with_gl_profile(GlProfile::Core)
+            .build_windowed(window_builder, &event_loop)
+            .expect("Failed to create window");
+        let windowed_context = unsafe {
+            windowed_context
+                .make_current()
+                .expect("Failed to make GL context current")
+        };
+
+        let gl = Gl::new(unsafe {
+            GlFns::load_from(&|name| windowed_context.get_proc_address(name) as *const _)
+                .expect("Failed to load GL functions")
+        });
+        let graphics = Graphics::new(gl);
+
+        // `WindowEvent::Resized`/`CursorMoved` both report physical pixels, so seed `Context`
+        // with the window's physical size rather than the logical size used to build it —
+        // otherwise the two would disagree on any display with a non-1.0 scale factor.
+        let physical_size = windowed_context.window().inner_size();
+        let context = Context::new(physical_size.width, physical_size.height);
+
+        (
+            Game {
+                event_loop,
+                windowed_context,
+                graphics,
+                context: context.clone(),
+            },
+            context,
+        )
+    }
+}
+
+pub struct Game {
+    event_loop: EventLoop<()>,
+    windowed_context: ContextWrapper<PossiblyCurrent, Window>,
+    graphics: Graphics,
+    context: Context,
+}
+
+impl Game {
+    pub fn poll<F: FnMut(Event)>(self, mut event_handler: F) -> ! {
+        let Game {
+            event_loop,
+            windowed_context,
+            mut graphics,
+            context,
+        } = self;
+
+        let mut last_update = Instant::now();
+
+        event_loop.run(move |event, _, control_flow| {
+            *control_flow = ControlFlow::Poll;
+
+            match event {
+                GlutinEvent::WindowEvent { event, .. } => match event {
+                    WindowEvent::CloseRequested => *control_flow = ControlFlow::Exit,
+                    WindowEvent::Resized(size) => {
+                        context.set_size(size.width, size.height);
+                        graphics.set_viewport(size.width, size.height);
+                        event_handler(Event::WindowResized(size.width, size.height));
+                    }
+                    WindowEvent::CursorMoved { position, .. } => {
+                        event_handler(Event::MouseMoved(position.x as f32, position.y as f32));
+                    }
+                    WindowEvent::MouseInput { state, button, .. } => {
+                        let button = convert_mouse_button(button);
+                        event_handler(match state {
+                            ElementState::Pressed => Event::MouseDown(button),
+                            ElementState::Released => Event::MouseUp(button),
+                        });
+                    }
+                    WindowEvent::KeyboardInput { input, .. } => {
+                        if let Some(key) = input.virtual_keycode.and_then(convert_key) {
+                            event_handler(match input.state {
+                                ElementState::Pressed => Event::KeyDown(key),
+                                ElementState::Released => Event::KeyUp(key),
+                            });
+                        }
+                    }
+                    _ => {}
+                },
+                GlutinEvent::MainEventsCleared => {
+                    windowed_context.window().request_redraw();
+                }
+                GlutinEvent::RedrawRequested(_) => {
+                    let now = Instant::now();
+                    let delta = (now - last_update).as_secs_f32();
+                    last_update = now;
+                    context.push_frame_time(delta);
+
+                    event_handler(Event::Update(delta));
+                    event_handler(Event::Draw(&mut graphics));
+                    windowed_context.swap_buffers().unwrap();
+                }
+                _ => {}
+            }
+        })
+    }
+}
+
+fn convert_mouse_button(button: GlutinMouseButton) -> MouseButton {
+    match button {
+        GlutinMouseButton::Left => MouseButton::Left,
+        GlutinMouseButton::Right => MouseButton::Right,
+        GlutinMouseButton::Middle => MouseButton::Middle,
+        GlutinMouseButton::Other(id) => MouseButton::Other(id),
+    }
+}
+
+fn convert_key(key: VirtualKeyCode) -> Option<Key> {
+    use VirtualKeyCode::*;
+    Some(match key {
+        A => Key::A,
+        B => Key::B,
+        C => Key::C,
+        D => Key::D,
+        E => Key::E,
+        F => Key::F,
+        G => Key::G,
+        H => Key::H,
+        I => Key::I,
+        J => Key::J,
+        K => Key::K,
+        L => Key::L,
+        M => Key::M,
+        N => Key::N,
+        O => Key::O,
+        P => Key::P,
+        Q => Key::Q,
+        R => Key::R,
+        S => Key::S,
+        T => Key::T,
+        U => Key::U,
+        V => Key::V,
+        W => Key::W,
+        X => Key::X,
+        Y => Key::Y,
+        Z => Key::Z,
+        Key0 => Key::Key0,
+        Key1 => Key::Key1,
+        Key2 => Key::Key2,
+        Key3 => Key::Key3,
+        Key4 => Key::Key4,
+        Key5 => Key::Key5,
+        Key6 => Key::Key6,
+        Key7 => Key::Key7,
+        Key8 => Key::Key8,
+        Key9 => Key::Key9,
+        Up => Key::Up,
+        Down => Key::Down,
+        Left => Key::Left,
+        Right => Key::Right,
+        Space => Key::Space,
+        Return => Key::Enter,
+        Escape => Key::Escape,
+        Tab => Key::Tab,
+        Back => Key::Backspace,
+        LShift | RShift => Key::Shift,
+        LControl | RControl => Key::Control,
+        LAlt | RAlt => Key::Alt,
+        _ => return None,
+    })
+}
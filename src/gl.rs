@@ -61,13 +61,41 @@ impl Gl {
         }
     }
 
+    pub unsafe fn create_texture(&self, width: i32, height: i32, pixels: &[u8]) -> u32 {
+        let mut texture = 0;
+
+        self.GenTextures(1, &mut texture);
+        self.BindTexture(GL_TEXTURE_2D, texture);
+        self.TexImage2D(
+            GL_TEXTURE_2D,
+            0,
+            GL_RGBA as _,
+            width,
+            height,
+            0,
+            GL_RGBA,
+            GL_UNSIGNED_BYTE,
+            pixels.as_ptr().cast(),
+        );
+        self.TexParameteri(GL_TEXTURE_2D, GL_TEXTURE_MIN_FILTER, GL_LINEAR as _);
+        self.TexParameteri(GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER, GL_LINEAR as _);
+
+        texture
+    }
+
+    /// Creates a framebuffer with a color texture attachment, optionally backed by a combined
+    /// depth/stencil renderbuffer. Returns `(fbo, texture, renderbuffer)`, where `renderbuffer`
+    /// is `0` (and safe to pass to `DeleteRenderbuffers`) when `with_depth_stencil` is `false`.
+    #[allow(clippy::too_many_arguments)]
     pub unsafe fn create_framebuffer(
         &self,
         width: i32,
         height: i32,
+        internal_format: u32,
         format: u32,
         kind: u32,
-    ) -> (u32, u32) {
+        with_depth_stencil: bool,
+    ) -> (u32, u32, u32) {
         let mut fbo = 0;
         let mut texture = 0;
 
@@ -79,7 +107,7 @@ impl Gl {
         self.TexImage2D(
             GL_TEXTURE_2D,
             0,
-            GL_RGB as _,
+            internal_format as _,
             width,
             height,
             0,
@@ -98,7 +126,24 @@ impl Gl {
             0,
         );
 
-        (fbo, texture)
+        let mut renderbuffer = 0;
+        if with_depth_stencil {
+            self.GenRenderbuffers(1, &mut renderbuffer);
+            self.BindRenderbuffer(GL_RENDERBUFFER, renderbuffer);
+            self.RenderbufferStorage(GL_RENDERBUFFER, GL_DEPTH24_STENCIL8, width, height);
+            self.FramebufferRenderbuffer(
+                GL_FRAMEBUFFER,
+                GL_DEPTH_STENCIL_ATTACHMENT,
+                GL_RENDERBUFFER,
+                renderbuffer,
+            );
+        }
+
+        // Leave the default framebuffer bound; callers shouldn't have to know this function
+        // rebinds GL_FRAMEBUFFER while it sets the new target up.
+        self.BindFramebuffer(GL_FRAMEBUFFER, 0);
+
+        (fbo, texture, renderbuffer)
     }
 
     pub unsafe fn create_program(
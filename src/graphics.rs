@@ -1,13 +1,20 @@
 use gl33::*;
+use glam::{Affine2, Vec2};
 
 use crate::gl::Gl;
+use crate::BlendMode;
 use crate::Color;
+use crate::Font;
+use crate::Texture;
 
 pub struct Graphics {
     gl: Gl,
     vbo: u32,
     program: u32,
     viewport_uniform: i32,
+    blank_texture: u32,
+    width: u32,
+    height: u32,
 }
 
 impl Drop for Graphics {
@@ -15,6 +22,7 @@ impl Drop for Graphics {
         unsafe {
             self.gl.DeleteBuffers(1, &self.vbo);
             self.gl.DeleteProgram(self.program);
+            self.gl.DeleteTextures(1, &self.blank_texture);
         }
     }
 }
@@ -78,32 +86,26 @@ impl Graphics {
             let viewport_uniform = gl.get_uniform_location(program, "uViewport");
             gl.Uniform2f(viewport_uniform, viewport[2] as _, viewport[3] as _);
 
-            let mut blank_texture = 0;
-            gl.GenTextures(1, &mut blank_texture);
-            gl.BindTexture(GL_TEXTURE_2D, blank_texture);
             gl.ActiveTexture(GL_TEXTURE0);
-            gl.TexImage2D(
-                GL_TEXTURE_2D,
-                0,
-                GL_RGB as _,
-                1,
-                1,
-                0,
-                GL_RGB,
-                GL_UNSIGNED_BYTE,
-                [255_u8, 255_u8, 255_u8].as_ptr().cast(),
-            );
+            let blank_texture = gl.create_texture(1, 1, &[255, 255, 255, 255]);
+
+            BlendMode::Alpha.apply(&gl);
 
             Self {
                 gl,
                 vbo,
                 program,
                 viewport_uniform,
+                blank_texture,
+                width: viewport[2] as _,
+                height: viewport[3] as _,
             }
         }
     }
 
     pub(crate) fn set_viewport(&mut self, width: u32, height: u32) {
+        self.width = width;
+        self.height = height;
         unsafe {
             self.gl.Viewport(0, 0, width as _, height as _);
             self.gl
@@ -119,50 +121,109 @@ impl Graphics {
     }
 
     pub fn push(&mut self) -> GraphicsScope<'_> {
+        self.push_to(RenderTarget::Window)
+    }
+
+    pub(crate) fn push_to(&mut self, target: RenderTarget) -> GraphicsScope<'_> {
         GraphicsScope {
             graphics: self,
             depth: 0.,
             color: Color::WHITE,
+            blend_mode: BlendMode::Alpha,
+            transform: Affine2::IDENTITY,
+            target,
             commands: Vec::new(),
         }
     }
 }
 
+#[derive(Clone, Copy)]
+pub(crate) enum RenderTarget {
+    Window,
+    Canvas { fbo: u32, width: u32, height: u32 },
+}
+
 pub struct GraphicsScope<'a> {
     graphics: &'a mut Graphics,
     depth: f32,
     color: Color,
+    blend_mode: BlendMode,
+    transform: Affine2,
+    target: RenderTarget,
     commands: Vec<Command>,
 }
 
 struct Command {
     verts: Vec<f32>,
     depth: f32,
+    texture: u32,
+    blend_mode: BlendMode,
 }
 
+#[derive(Debug, PartialEq)]
 struct Batch {
-    vert_count: usize,
+    start_vertex: usize,
+    vertex_count: usize,
+    texture: u32,
+    blend_mode: BlendMode,
 }
 
-impl Drop for GraphicsScope<'_> {
-    fn drop(&mut self) {
-        let mut batches = Vec::new();
-        let mut verts = Vec::<f32>::new();
-        self.commands
-            .sort_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap());
-        for command in self.commands.iter() {
-            verts.extend(command.verts.iter());
-            if batches.is_empty() {
-                batches.push(Batch { vert_count: 0 });
+/// Sorts `commands` by depth and groups consecutive same-texture, same-blend-mode commands into
+/// batches, returning the batches alongside the flattened vertex buffer they index into.
+fn batch_commands(commands: &mut [Command]) -> (Vec<Batch>, Vec<f32>) {
+    let mut batches = Vec::new();
+    let mut verts = Vec::<f32>::new();
+    commands.sort_by(|a, b| a.depth.partial_cmp(&b.depth).unwrap());
+    for command in commands.iter() {
+        let vertex_count = command.verts.len() / 8;
+        verts.extend(command.verts.iter());
+
+        match batches.last_mut() {
+            Some(batch)
+                if batch.texture == command.texture && batch.blend_mode == command.blend_mode =>
+            {
+                batch.vertex_count += vertex_count;
             }
-            batches[0].vert_count += command.verts.len();
+            _ => batches.push(Batch {
+                start_vertex: verts.len() / 8 - vertex_count,
+                vertex_count,
+                texture: command.texture,
+                blend_mode: command.blend_mode,
+            }),
         }
+    }
+    (batches, verts)
+}
+
+impl Drop for GraphicsScope<'_> {
+    fn drop(&mut self) {
+        let (batches, verts) = batch_commands(&mut self.commands);
         self.commands.clear();
         if verts.is_empty() {
             return;
         }
 
         unsafe {
+            match self.target {
+                RenderTarget::Canvas { fbo, width, height } => {
+                    self.graphics.gl.BindFramebuffer(GL_FRAMEBUFFER, fbo);
+                    self.graphics.gl.Viewport(0, 0, width as _, height as _);
+                    self.graphics
+                        .gl
+                        .Uniform2f(self.graphics.viewport_uniform, width as _, height as _);
+                }
+                RenderTarget::Window => {
+                    // Don't assume framebuffer 0 is already current; a prior Canvas scope (or
+                    // anything else that bound an FBO) may not have restored it.
+                    let (width, height) = (self.graphics.width, self.graphics.height);
+                    self.graphics.gl.BindFramebuffer(GL_FRAMEBUFFER, 0);
+                    self.graphics.gl.Viewport(0, 0, width as _, height as _);
+                    self.graphics
+                        .gl
+                        .Uniform2f(self.graphics.viewport_uniform, width as _, height as _);
+                }
+            }
+
             self.graphics.gl.BufferData(
                 GL_ARRAY_BUFFER,
                 (std::mem::size_of::<f32>() * verts.len()) as isize,
@@ -170,19 +231,42 @@ impl Drop for GraphicsScope<'_> {
                 GL_STREAM_DRAW,
             );
 
-            let mut index = 0;
             for batch in batches {
+                batch.blend_mode.apply(&self.graphics.gl);
+                self.graphics.gl.BindTexture(GL_TEXTURE_2D, batch.texture);
                 self.graphics.gl.DrawArrays(
                     GL_TRIANGLES,
-                    index as _,
-                    (index + batch.vert_count) as _,
+                    batch.start_vertex as _,
+                    batch.vertex_count as _,
                 );
-                index += batch.vert_count;
+            }
+
+            if matches!(self.target, RenderTarget::Canvas { .. }) {
+                let (width, height) = (self.graphics.width, self.graphics.height);
+                self.graphics.gl.BindFramebuffer(GL_FRAMEBUFFER, 0);
+                self.graphics.gl.Viewport(0, 0, width as _, height as _);
+                self.graphics
+                    .gl
+                    .Uniform2f(self.graphics.viewport_uniform, width as _, height as _);
             }
         }
     }
 }
 
+/// Applies a translation on top of `transform`, so that it takes effect before whatever `transform`
+/// already composes (i.e. the newest call acts "closest" to the points being transformed).
+fn compose_translate(transform: Affine2, x: f32, y: f32) -> Affine2 {
+    transform * Affine2::from_translation(Vec2::new(x, y))
+}
+
+fn compose_rotate(transform: Affine2, radians: f32) -> Affine2 {
+    transform * Affine2::from_angle(radians)
+}
+
+fn compose_scale(transform: Affine2, x: f32, y: f32) -> Affine2 {
+    transform * Affine2::from_scale(Vec2::new(x, y))
+}
+
 impl GraphicsScope<'_> {
     pub fn set_depth(&mut self, depth: f32) {
         self.depth = depth;
@@ -192,59 +276,290 @@ impl GraphicsScope<'_> {
         self.color = color;
     }
 
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    pub fn push(&mut self) -> GraphicsScope<'_> {
+        GraphicsScope {
+            graphics: &mut *self.graphics,
+            depth: self.depth,
+            color: self.color,
+            blend_mode: self.blend_mode,
+            transform: self.transform,
+            target: self.target,
+            commands: Vec::new(),
+        }
+    }
+
+    pub fn translate(&mut self, x: f32, y: f32) {
+        self.transform = compose_translate(self.transform, x, y);
+    }
+
+    pub fn rotate(&mut self, radians: f32) {
+        self.transform = compose_rotate(self.transform, radians);
+    }
+
+    pub fn scale(&mut self, x: f32, y: f32) {
+        self.transform = compose_scale(self.transform, x, y);
+    }
+
     pub fn draw_rect(&mut self, x: f32, y: f32, width: f32, height: f32) {
-        self.commands.push(Command {
-            verts: vec![
-                x,
-                y,
-                0.,
-                1.,
-                self.color.r,
-                self.color.g,
-                self.color.b,
-                self.color.a,
-                x,
-                y + height,
-                0.,
-                0.,
-                self.color.r,
-                self.color.g,
-                self.color.b,
-                self.color.a,
-                x + width,
-                y,
-                1.,
-                1.,
-                self.color.r,
-                self.color.g,
-                self.color.b,
-                self.color.a,
-                x + width,
-                y,
-                1.,
-                1.,
-                self.color.r,
-                self.color.g,
-                self.color.b,
-                self.color.a,
-                x,
-                y + height,
-                0.,
-                0.,
-                self.color.r,
-                self.color.g,
-                self.color.b,
-                self.color.a,
-                x + width,
-                y + height,
-                1.,
-                0.,
+        let texture = self.graphics.blank_texture;
+        self.push_quad(texture, x, y, width, height, 0., 1., 1., 0.);
+    }
+
+    pub fn draw_texture(&mut self, texture: &Texture, x: f32, y: f32) {
+        self.draw_texture_region(
+            texture,
+            0.,
+            0.,
+            texture.width as f32,
+            texture.height as f32,
+            x,
+            y,
+            texture.width as f32,
+            texture.height as f32,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_texture_region(
+        &mut self,
+        texture: &Texture,
+        src_x: f32,
+        src_y: f32,
+        src_width: f32,
+        src_height: f32,
+        dst_x: f32,
+        dst_y: f32,
+        dst_width: f32,
+        dst_height: f32,
+    ) {
+        let u0 = src_x / texture.width as f32;
+        let mut v0 = src_y / texture.height as f32;
+        let u1 = (src_x + src_width) / texture.width as f32;
+        let mut v1 = (src_y + src_height) / texture.height as f32;
+
+        // Canvas-backed textures are stored bottom-to-top in texel space, unlike file-loaded
+        // ones; flip so `src_y = 0` still means "top of what was drawn" to callers.
+        if texture.flip_y {
+            v0 = 1. - v0;
+            v1 = 1. - v1;
+        }
+
+        self.push_quad(texture.id, dst_x, dst_y, dst_width, dst_height, u0, v0, u1, v1);
+    }
+
+    pub fn draw_text(&mut self, font: &Font, text: &str, x: f32, y: f32) {
+        let mut pen_x = x;
+        let mut pen_y = y;
+
+        for ch in text.chars() {
+            if ch == '\n' {
+                pen_x = x;
+                pen_y += font.line_height;
+                continue;
+            }
+
+            let glyph = match font.glyphs.get(&ch) {
+                Some(glyph) => glyph,
+                None => {
+                    pen_x += font.space_advance;
+                    continue;
+                }
+            };
+
+            let u0 = glyph.x / font.atlas_width;
+            let v0 = glyph.y / font.atlas_height;
+            let u1 = (glyph.x + glyph.width) / font.atlas_width;
+            let v1 = (glyph.y + glyph.height) / font.atlas_height;
+
+            self.push_quad(
+                font.texture.id,
+                pen_x - glyph.origin_x,
+                pen_y - glyph.origin_y,
+                glyph.width,
+                glyph.height,
+                u0,
+                v0,
+                u1,
+                v1,
+            );
+
+            pen_x += glyph.advance;
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn push_quad(
+        &mut self,
+        texture: u32,
+        dst_x: f32,
+        dst_y: f32,
+        dst_width: f32,
+        dst_height: f32,
+        u0: f32,
+        v0: f32,
+        u1: f32,
+        v1: f32,
+    ) {
+        let top_left = self.transform.transform_point2(Vec2::new(dst_x, dst_y));
+        let bottom_left = self
+            .transform
+            .transform_point2(Vec2::new(dst_x, dst_y + dst_height));
+        let top_right = self
+            .transform
+            .transform_point2(Vec2::new(dst_x + dst_width, dst_y));
+        let bottom_right = self
+            .transform
+            .transform_point2(Vec2::new(dst_x + dst_width, dst_y + dst_height));
+
+        let mut verts = Vec::with_capacity(48);
+        for (point, u, v) in [
+            (top_left, u0, v0),
+            (bottom_left, u0, v1),
+            (top_right, u1, v0),
+            (top_right, u1, v0),
+            (bottom_left, u0, v1),
+            (bottom_right, u1, v1),
+        ] {
+            verts.extend([
+                point.x,
+                point.y,
+                u,
+                v,
                 self.color.r,
                 self.color.g,
                 self.color.b,
                 self.color.a,
-            ],
+            ]);
+        }
+
+        self.commands.push(Command {
+            texture,
+            blend_mode: self.blend_mode,
+            verts,
             depth: self.depth,
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(depth: f32, texture: u32, blend_mode: BlendMode) -> Command {
+        Command {
+            verts: vec![0.; 8],
+            depth,
+            texture,
+            blend_mode,
+        }
+    }
+
+    #[test]
+    fn consecutive_commands_with_same_texture_and_blend_mode_merge_into_one_batch() {
+        let mut commands = vec![
+            command(0., 1, BlendMode::Alpha),
+            command(1., 1, BlendMode::Alpha),
+        ];
+        let (batches, verts) = batch_commands(&mut commands);
+
+        assert_eq!(
+            batches,
+            vec![Batch {
+                start_vertex: 0,
+                vertex_count: 2,
+                texture: 1,
+                blend_mode: BlendMode::Alpha,
+            }]
+        );
+        assert_eq!(verts.len(), 16);
+    }
+
+    #[test]
+    fn a_different_texture_or_blend_mode_starts_a_new_batch() {
+        let mut commands = vec![
+            command(0., 1, BlendMode::Alpha),
+            command(1., 2, BlendMode::Alpha),
+            command(2., 2, BlendMode::Additive),
+        ];
+        let (batches, _) = batch_commands(&mut commands);
+
+        assert_eq!(
+            batches,
+            vec![
+                Batch {
+                    start_vertex: 0,
+                    vertex_count: 1,
+                    texture: 1,
+                    blend_mode: BlendMode::Alpha,
+                },
+                Batch {
+                    start_vertex: 1,
+                    vertex_count: 1,
+                    texture: 2,
+                    blend_mode: BlendMode::Alpha,
+                },
+                Batch {
+                    start_vertex: 2,
+                    vertex_count: 1,
+                    texture: 2,
+                    blend_mode: BlendMode::Additive,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_are_batched_in_depth_order_not_insertion_order() {
+        let mut commands = vec![
+            command(1., 2, BlendMode::Alpha),
+            command(0., 1, BlendMode::Alpha),
+        ];
+        let (batches, _) = batch_commands(&mut commands);
+
+        assert_eq!(batches[0].texture, 1);
+        assert_eq!(batches[1].texture, 2);
+    }
+
+    #[test]
+    fn translate_then_rotate_applies_the_rotation_to_the_point_first() {
+        let transform = compose_translate(Affine2::IDENTITY, 10., 0.);
+        let transform = compose_rotate(transform, std::f32::consts::FRAC_PI_2);
+
+        // Composing a later call on the right (`transform * NewOp`) means the later call is
+        // applied to the point first: rotating (1, 0) by 90° gives (0, 1), which the earlier
+        // translate(10, 0) then shifts to (10, 1). At the origin the rotation would have no
+        // visible effect, since rotation fixes it — use a point off the origin.
+        let p = transform.transform_point2(Vec2::new(1., 0.));
+        assert!((p.x - 10.).abs() < 1e-5, "x was {}", p.x);
+        assert!((p.y - 1.).abs() < 1e-5, "y was {}", p.y);
+    }
+
+    #[test]
+    fn rotate_then_translate_applies_the_translation_to_the_point_first() {
+        let transform = compose_rotate(Affine2::IDENTITY, std::f32::consts::FRAC_PI_2);
+        let transform = compose_translate(transform, 10., 0.);
+
+        // Reversing the call order reverses which op sees the raw point first: translate(10, 0)
+        // moves the origin to (10, 0), which the earlier rotate(90°) then turns into (0, 10).
+        let p = transform.transform_point2(Vec2::new(0., 0.));
+        assert!((p.x - 0.).abs() < 1e-5, "x was {}", p.x);
+        assert!((p.y - 10.).abs() < 1e-5, "y was {}", p.y);
+    }
+
+    #[test]
+    fn translate_then_scale_applies_the_scale_to_the_point_first() {
+        let transform = compose_translate(Affine2::IDENTITY, 10., 0.);
+        let transform = compose_scale(transform, 2., 2.);
+
+        // Scale(2, 2) is applied to the point first (1, 1) -> (2, 2), then the earlier
+        // translate(10, 0) shifts it to (12, 2). At the origin the scale would have no visible
+        // effect either, since scaling fixes it.
+        let p = transform.transform_point2(Vec2::new(1., 1.));
+        assert!((p.x - 12.).abs() < 1e-5, "x was {}", p.x);
+        assert!((p.y - 2.).abs() < 1e-5, "y was {}", p.y);
+    }
+}
@@ -1,12 +1,20 @@
 mod gl;
 
+mod blend;
+pub use blend::BlendMode;
+mod canvas;
+pub use canvas::Canvas;
 mod color;
 pub use color::Color;
 mod context;
 pub use context::Context;
 mod event;
-pub use event::Event;
+pub use event::{Event, Key, MouseButton};
+mod font;
+pub use font::Font;
 mod game;
 pub use game::{Game, GameBuilder};
 mod graphics;
-pub use graphics::Graphics;
\ No newline at end of file
+pub use graphics::{Graphics, GraphicsScope};
+mod texture;
+pub use texture::Texture;
\ No newline at end of file
@@ -0,0 +1,55 @@
+use crate::gl::Gl;
+
+pub struct Texture {
+    gl: Gl,
+    pub(crate) id: u32,
+    pub(crate) width: u32,
+    pub(crate) height: u32,
+    /// Whether this texture's rows are stored bottom-to-top in GL's texel space, as is the case
+    /// for anything rendered into a `Canvas` (row 0 of the color attachment is NDC `y = -1`,
+    /// i.e. the bottom of the image, unlike a file-loaded texture's row 0 being the top).
+    pub(crate) flip_y: bool,
+}
+
+impl Drop for Texture {
+    fn drop(&mut self) {
+        unsafe {
+            self.gl.DeleteTextures(1, &self.id);
+        }
+    }
+}
+
+impl Texture {
+    pub fn from_file(gl: Gl, path: &str) -> Result<Self, String> {
+        let image = image::open(path).map_err(|e| e.to_string())?.to_rgba8();
+        let (width, height) = image.dimensions();
+
+        let id = unsafe { gl.create_texture(width as _, height as _, &image) };
+
+        Ok(Self {
+            gl,
+            id,
+            width,
+            height,
+            flip_y: false,
+        })
+    }
+
+    pub(crate) fn from_raw(gl: Gl, id: u32, width: u32, height: u32) -> Self {
+        Self {
+            gl,
+            id,
+            width,
+            height,
+            flip_y: true,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+}